@@ -0,0 +1,126 @@
+//! Steady-state streaming: push a chunk, consume one, keeping roughly half
+//! the buffer filled. `Vec<u8>` and `VecDeque<u8>` stand in for a growable
+//! buffer such as `BytesMut`, which cannot be fetched here.
+//!
+//! ```text
+//! cargo bench -p bytes
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::VecDeque;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use bytes::RingBuffer;
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const CAPACITY: usize = 64 * 1024;
+const CHUNK: usize = 1500;
+const STEPS: usize = 200_000;
+const RUNS: usize = 5;
+
+/// The operations each buffer needs for the streaming loop.
+trait Buffer {
+    fn push(&mut self, data: &[u8]);
+    /// Copies up to `out.len()` bytes from the front into `out` and drops them.
+    fn pop(&mut self, out: &mut [u8]) -> usize;
+}
+
+impl Buffer for RingBuffer {
+    fn push(&mut self, data: &[u8]) {
+        self.push_slice(data);
+    }
+
+    fn pop(&mut self, out: &mut [u8]) -> usize {
+        let count = self.peek(out);
+        self.consume(count);
+        count
+    }
+}
+
+impl Buffer for Vec<u8> {
+    fn push(&mut self, data: &[u8]) {
+        self.extend_from_slice(data);
+    }
+
+    fn pop(&mut self, out: &mut [u8]) -> usize {
+        let count = out.len().min(self.len());
+        out[..count].copy_from_slice(&self[..count]);
+        self.drain(..count);
+        count
+    }
+}
+
+impl Buffer for VecDeque<u8> {
+    fn push(&mut self, data: &[u8]) {
+        self.extend(data);
+    }
+
+    fn pop(&mut self, out: &mut [u8]) -> usize {
+        let (a, b) = self.as_slices();
+        let count = out.len().min(self.len());
+        let first = count.min(a.len());
+        out[..first].copy_from_slice(&a[..first]);
+        out[first..count].copy_from_slice(&b[..count - first]);
+        self.drain(..count);
+        count
+    }
+}
+
+fn run<B: Buffer>(name: &str, mut make: impl FnMut() -> B) {
+    let chunk: Vec<u8> = (0..CHUNK).map(|i| i as u8).collect();
+    let mut out = vec![0; CHUNK];
+    let mut best = Duration::MAX;
+    let mut allocations = 0;
+    for _ in 0..RUNS {
+        let mut buffer = make();
+        for _ in 0..CAPACITY / CHUNK / 2 {
+            buffer.push(&chunk);
+        }
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let mut total = 0;
+        for _ in 0..STEPS {
+            buffer.push(black_box(&chunk));
+            total += buffer.pop(&mut out);
+        }
+        black_box((total, &out));
+        best = best.min(start.elapsed());
+        allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    }
+    let mib = (STEPS * CHUNK) as f64 / (1024.0 * 1024.0);
+    println!(
+        "{name:>12}: {best:>10.2?}  {:>8.0} MiB/s  {allocations} allocations",
+        mib / best.as_secs_f64()
+    );
+}
+
+fn main() {
+    println!("{STEPS} steps of {CHUNK}-byte push + consume, {CAPACITY}-byte buffers");
+    run("RingBuffer", || RingBuffer::with_capacity(CAPACITY));
+    run("Vec<u8>", || Vec::with_capacity(CAPACITY));
+    run("VecDeque<u8>", || VecDeque::with_capacity(CAPACITY));
+}
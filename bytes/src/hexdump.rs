@@ -0,0 +1,95 @@
+use std::fmt;
+
+const BYTES_PER_LINE: usize = 16;
+
+/// `hexdump -C` style display adapter: offset column, hex bytes and an ASCII gutter.
+///
+/// The formatter precision limits the number of lines, so
+/// `format!("{:.4}", Hexdump(bytes))` shows at most 64 bytes followed by a
+/// note of how many were left out.
+#[derive(Debug, Clone, Copy)]
+pub struct Hexdump<'a>(pub &'a [u8]);
+
+impl fmt::Display for Hexdump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max_lines = f.precision().unwrap_or(usize::MAX);
+        for (line, chunk) in self.0.chunks(BYTES_PER_LINE).enumerate() {
+            if line > 0 {
+                writeln!(f)?;
+            }
+            if line == max_lines {
+                let hidden = self.0.len() - line * BYTES_PER_LINE;
+                let unit = if hidden == 1 { "byte" } else { "bytes" };
+                return write!(f, "... {hidden} more {unit}");
+            }
+            write!(f, "{:08x} ", line * BYTES_PER_LINE)?;
+            for i in 0..BYTES_PER_LINE {
+                if i % 8 == 0 {
+                    write!(f, " ")?;
+                }
+                match chunk.get(i) {
+                    Some(byte) => write!(f, "{byte:02X} ")?,
+                    None => write!(f, "   ")?,
+                }
+            }
+            write!(f, " |")?;
+            for &byte in chunk {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+            write!(f, "|")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_LINE: &str =
+        "00000000  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|";
+
+    #[test]
+    fn partial_line_pads_hex_so_gutter_aligns() {
+        let out = Hexdump(b"0123456789abcdefhi\x00 ~\x7f").to_string();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                FULL_LINE,
+                "00000010  68 69 00 20 7E 7F                                 |hi. ~.|",
+            ]
+        );
+        assert_eq!(lines[0].find('|'), lines[1].find('|'));
+    }
+
+    #[test]
+    fn empty_input_prints_nothing() {
+        assert_eq!(Hexdump(&[]).to_string(), "");
+        assert_eq!(format!("{:.0}", Hexdump(&[])), "");
+    }
+
+    #[test]
+    fn zero_precision_only_notes_length() {
+        assert_eq!(format!("{:.0}", Hexdump(b"x")), "... 1 more byte");
+        assert_eq!(format!("{:.0}", Hexdump(b"xyz")), "... 3 more bytes");
+    }
+
+    #[test]
+    fn precision_at_line_boundary() {
+        let data = b"0123456789abcdef";
+        assert_eq!(format!("{:.1}", Hexdump(data)), FULL_LINE);
+        assert_eq!(Hexdump(data).to_string(), FULL_LINE);
+
+        let data = b"0123456789abcdef0123456789abcdef";
+        assert_eq!(
+            format!("{:.1}", Hexdump(data)),
+            format!("{FULL_LINE}\n... 16 more bytes")
+        );
+    }
+}
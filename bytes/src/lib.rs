@@ -0,0 +1,12 @@
+mod hexdump;
+mod ring_buffer;
+mod utf8_validator;
+mod varint;
+
+pub use hexdump::Hexdump;
+pub use ring_buffer::RingBuffer;
+pub use utf8_validator::{Utf8StreamError, Utf8Validator};
+pub use varint::{
+    decode_signed_varint, decode_varint, encode_signed_varint, encode_varint, zigzag_decode,
+    zigzag_encode, VarintError, MAX_VARINT_LEN,
+};
@@ -0,0 +1,48 @@
+use bytes::{
+    decode_signed_varint, decode_varint, encode_signed_varint, encode_varint, Hexdump, RingBuffer,
+    Utf8Validator,
+};
+
+fn main() {
+    let mut buf = Vec::new();
+    for n in [0, 1, 127, 128, 300, u64::MAX] {
+        encode_varint(n, &mut buf);
+    }
+    encode_signed_varint(-1, &mut buf);
+    encode_signed_varint(i64::MIN, &mut buf);
+    println!("{} bytes:\n{}", buf.len(), Hexdump(&buf));
+
+    let mut input = buf.as_slice();
+    for _ in 0..6 {
+        println!("{:?}", decode_varint(&mut input));
+    }
+    println!("{:?}", decode_signed_varint(&mut input));
+    println!("{:?}", decode_signed_varint(&mut input));
+    println!("{:?}", decode_varint(&mut input));
+    println!("{:?}", decode_varint(&mut [0xFF; 11].as_slice()));
+
+    let mut ring = RingBuffer::with_capacity(8);
+    println!("pushed {}", ring.push_slice(b"hello"));
+    ring.consume(3);
+    println!("pushed {}", ring.push_slice(b" world!"));
+    println!("{:?}", ring.as_slices());
+    let mut peeked = [0; 4];
+    let n = ring.peek(&mut peeked);
+    println!("peeked {:?}", &peeked[..n]);
+    println!("{:?}", String::from_utf8_lossy(ring.make_contiguous()));
+    println!("len {}, free {}", ring.len(), ring.free());
+
+    let text = b"The quick brown fox jumps over the lazy dog.\n".repeat(3);
+    println!("{:.2}", Hexdump(&text));
+
+    let mut validator = Utf8Validator::new();
+    for chunk in "tags: café, 日本, 🦀".as_bytes().chunks(3) {
+        println!("{:?} -> {:?}", chunk, validator.feed(chunk));
+    }
+    println!("finish: {:?}", validator.finish());
+
+    let mut validator = Utf8Validator::new();
+    println!("{:?}", validator.feed(b"ok \xE2\x82"));
+    println!("{:?}", validator.finish());
+    println!("{:?}", validator.feed(b"\x41"));
+}
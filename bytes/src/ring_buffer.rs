@@ -0,0 +1,210 @@
+/// Fixed-capacity byte ring buffer. All storage is allocated up front;
+/// nothing allocates after [`RingBuffer::with_capacity`].
+#[derive(Debug, Clone)]
+pub struct RingBuffer {
+    buf: Box<[u8]>,
+    head: usize,
+    len: usize,
+}
+
+impl RingBuffer {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: vec![0; capacity].into_boxed_slice(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    pub fn free(&self) -> usize {
+        self.capacity() - self.len
+    }
+
+    /// Appends as much of `data` as fits and returns how many bytes were written.
+    pub fn push_slice(&mut self, data: &[u8]) -> usize {
+        let count = data.len().min(self.free());
+        let tail = (self.head + self.len) % self.capacity().max(1);
+        let first = count.min(self.capacity() - tail);
+        self.buf[tail..tail + first].copy_from_slice(&data[..first]);
+        self.buf[..count - first].copy_from_slice(&data[first..count]);
+        self.len += count;
+        count
+    }
+
+    /// Copies up to `out.len()` bytes from the front without consuming them.
+    pub fn peek(&self, out: &mut [u8]) -> usize {
+        let (a, b) = self.as_slices();
+        let count = out.len().min(self.len);
+        let first = count.min(a.len());
+        out[..first].copy_from_slice(&a[..first]);
+        out[first..count].copy_from_slice(&b[..count - first]);
+        count
+    }
+
+    /// Drops `count` bytes from the front (clamped to the buffered length).
+    pub fn consume(&mut self, count: usize) {
+        let count = count.min(self.len);
+        self.len -= count;
+        self.head = if self.len == 0 {
+            0
+        } else {
+            (self.head + count) % self.capacity()
+        };
+    }
+
+    /// The buffered bytes in order; the second slice is non-empty when the data wraps.
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        let first = self.len.min(self.capacity() - self.head);
+        (
+            &self.buf[self.head..self.head + first],
+            &self.buf[..self.len - first],
+        )
+    }
+
+    /// Rotates the storage in place so the buffered bytes form one slice.
+    pub fn make_contiguous(&mut self) -> &[u8] {
+        if self.head + self.len > self.capacity() {
+            self.buf.rotate_left(self.head);
+            self.head = 0;
+        }
+        &self.buf[self.head..self.head + self.len]
+    }
+
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    fn contents(ring: &RingBuffer) -> Vec<u8> {
+        let (a, b) = ring.as_slices();
+        [a, b].concat()
+    }
+
+    /// A buffer of capacity 8 holding `6..11` with the tail wrapped to the front.
+    fn wrapped() -> RingBuffer {
+        let mut ring = RingBuffer::with_capacity(8);
+        ring.push_slice(&[0, 1, 2, 3, 4, 5, 6]);
+        ring.consume(6);
+        ring.push_slice(&[7, 8, 9, 10]);
+        ring
+    }
+
+    #[test]
+    fn push_when_full_writes_what_fits() {
+        let mut ring = RingBuffer::with_capacity(4);
+        assert_eq!(ring.push_slice(b"abc"), 3);
+        assert_eq!(ring.push_slice(b"def"), 1);
+        assert!(ring.is_full());
+        assert_eq!(ring.push_slice(b"g"), 0);
+        assert_eq!(contents(&ring), b"abcd");
+    }
+
+    #[test]
+    fn wrapped_data_splits_across_slices() {
+        let ring = wrapped();
+        assert_eq!(ring.as_slices(), (&[6, 7][..], &[8, 9, 10][..]));
+
+        let mut out = [0; 4];
+        assert_eq!(ring.peek(&mut out), 4);
+        assert_eq!(out, [6, 7, 8, 9]);
+        let mut out = [0; 16];
+        assert_eq!(ring.peek(&mut out), 5);
+        assert_eq!(&out[..5], [6, 7, 8, 9, 10]);
+        assert_eq!(ring.len(), 5);
+    }
+
+    #[test]
+    fn consume_clamps_and_resets_head() {
+        let mut ring = wrapped();
+        ring.consume(3);
+        assert_eq!(ring.as_slices(), (&[9, 10][..], &[][..]));
+        ring.consume(100);
+        assert!(ring.is_empty());
+        assert_eq!(ring.push_slice(&[1; 8]), 8);
+        assert_eq!(ring.as_slices(), (&[1; 8][..], &[][..]));
+    }
+
+    #[test]
+    fn make_contiguous_rotates_wrapped_data() {
+        let mut ring = wrapped();
+        assert_eq!(ring.make_contiguous(), [6, 7, 8, 9, 10]);
+        assert_eq!(ring.as_slices(), (&[6, 7, 8, 9, 10][..], &[][..]));
+        assert_eq!(ring.push_slice(&[11, 12, 13, 14]), 3);
+        assert_eq!(contents(&ring), [6, 7, 8, 9, 10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn zero_capacity_holds_nothing() {
+        let mut ring = RingBuffer::with_capacity(0);
+        assert!(ring.is_empty() && ring.is_full());
+        assert_eq!(ring.push_slice(b"abc"), 0);
+        assert_eq!(ring.peek(&mut [0; 4]), 0);
+        ring.consume(1);
+        assert_eq!(ring.as_slices(), (&[][..], &[][..]));
+        assert_eq!(ring.make_contiguous(), []);
+    }
+
+    #[test]
+    fn random_operations_match_vec_deque() {
+        let mut state = 0x9E37_79B9u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        for capacity in [1, 2, 3, 7, 16] {
+            let mut ring = RingBuffer::with_capacity(capacity);
+            let mut model = VecDeque::new();
+            for step in 0..5_000 {
+                let r = next();
+                let amount = (r >> 8) as usize % (capacity + 3);
+                match r % 5 {
+                    0 | 1 => {
+                        let data: Vec<u8> = (0..amount).map(|i| (step + i) as u8).collect();
+                        let written = ring.push_slice(&data);
+                        assert_eq!(written, amount.min(capacity - model.len()));
+                        model.extend(&data[..written]);
+                    }
+                    2 => {
+                        ring.consume(amount);
+                        model.drain(..amount.min(model.len()));
+                    }
+                    3 => {
+                        let mut out = vec![0; amount];
+                        let count = ring.peek(&mut out);
+                        assert_eq!(count, amount.min(model.len()));
+                        assert!(out[..count].iter().eq(model.iter().take(count)));
+                    }
+                    _ => {
+                        assert_eq!(ring.make_contiguous(), model.make_contiguous());
+                    }
+                }
+                assert_eq!(ring.len(), model.len());
+                assert!(contents(&ring).iter().eq(model.iter()));
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use std::fmt;
+use std::str;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8StreamError {
+    valid_up_to: usize,
+    error_len: Option<usize>,
+}
+
+impl Utf8StreamError {
+    /// Offset from the start of the stream up to which the input was valid.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Length of the invalid sequence, or `None` if the stream ended mid-sequence.
+    pub fn error_len(&self) -> Option<usize> {
+        self.error_len
+    }
+}
+
+impl fmt::Display for Utf8StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error_len {
+            Some(len) => write!(
+                f,
+                "invalid utf-8 sequence of {len} bytes at offset {}",
+                self.valid_up_to
+            ),
+            None => write!(
+                f,
+                "incomplete utf-8 sequence at end of stream, offset {}",
+                self.valid_up_to
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Utf8StreamError {}
+
+/// Incremental UTF-8 validator that accepts input in arbitrary chunks, including
+/// chunks that split a multi-byte sequence.
+#[derive(Debug, Clone, Default)]
+pub struct Utf8Validator {
+    valid_up_to: usize,
+    pending: [u8; 4],
+    pending_len: usize,
+    error: Option<Utf8StreamError>,
+}
+
+impl Utf8Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates the next chunk and returns the length of the valid prefix of
+    /// the whole stream so far. Once an error is found it is returned for every
+    /// later call.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<usize, Utf8StreamError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let rest = self.complete_pending(chunk)?;
+        match str::from_utf8(rest) {
+            Ok(_) => self.valid_up_to += rest.len(),
+            Err(e) => {
+                self.valid_up_to += e.valid_up_to();
+                match e.error_len() {
+                    Some(len) => return Err(self.fail(Some(len))),
+                    None => {
+                        let tail = &rest[e.valid_up_to()..];
+                        self.pending[..tail.len()].copy_from_slice(tail);
+                        self.pending_len = tail.len();
+                    }
+                }
+            }
+        }
+        Ok(self.valid_up_to)
+    }
+
+    /// Ends the stream, failing if it stopped in the middle of a sequence.
+    pub fn finish(&self) -> Result<usize, Utf8StreamError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if self.pending_len > 0 {
+            return Err(Utf8StreamError {
+                valid_up_to: self.valid_up_to,
+                error_len: None,
+            });
+        }
+        Ok(self.valid_up_to)
+    }
+
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Number of bytes held back because they start a sequence not yet complete.
+    pub fn pending(&self) -> usize {
+        self.pending_len
+    }
+
+    /// Feeds bytes into a sequence left over from the previous chunk and
+    /// returns the part of `chunk` after it.
+    fn complete_pending<'a>(&mut self, chunk: &'a [u8]) -> Result<&'a [u8], Utf8StreamError> {
+        let mut used = 0;
+        while self.pending_len > 0 {
+            let Some(&byte) = chunk.get(used) else {
+                return Ok(&[]);
+            };
+            self.pending[self.pending_len] = byte;
+            self.pending_len += 1;
+            used += 1;
+            match str::from_utf8(&self.pending[..self.pending_len]) {
+                Ok(_) => {
+                    self.valid_up_to += self.pending_len;
+                    self.pending_len = 0;
+                }
+                Err(e) => {
+                    if let Some(len) = e.error_len() {
+                        return Err(self.fail(Some(len)));
+                    }
+                }
+            }
+        }
+        Ok(&chunk[used..])
+    }
+
+    fn fail(&mut self, error_len: Option<usize>) -> Utf8StreamError {
+        let error = Utf8StreamError {
+            valid_up_to: self.valid_up_to,
+            error_len,
+        };
+        self.error = Some(error);
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate_in_chunks(input: &[u8], size: usize) -> Result<usize, Utf8StreamError> {
+        let mut validator = Utf8Validator::new();
+        for chunk in input.chunks(size) {
+            validator.feed(chunk)?;
+        }
+        validator.finish()
+    }
+
+    /// Every chunking of `input` must agree with `std::str::from_utf8`.
+    fn assert_matches_std(input: &[u8]) {
+        let expected = str::from_utf8(input);
+        for size in 1..=input.len().max(1) {
+            match (expected, validate_in_chunks(input, size)) {
+                (Ok(s), Ok(len)) => assert_eq!(len, s.len()),
+                (Err(e), Err(got)) => {
+                    assert_eq!(got.valid_up_to(), e.valid_up_to(), "{input:02X?} / {size}");
+                    assert_eq!(got.error_len(), e.error_len(), "{input:02X?} / {size}");
+                }
+                (expected, got) => panic!("{input:02X?} / {size}: {expected:?} vs {got:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn four_byte_sequence_split_into_single_bytes() {
+        let mut validator = Utf8Validator::new();
+        for (i, byte) in "🦀".bytes().enumerate() {
+            assert_eq!(validator.feed(&[byte]), Ok(if i == 3 { 4 } else { 0 }));
+        }
+        assert_eq!(validator.pending(), 0);
+        assert_eq!(validator.finish(), Ok(4));
+    }
+
+    #[test]
+    fn invalid_byte_after_pending_prefix() {
+        let mut validator = Utf8Validator::new();
+        assert_eq!(validator.feed(b"ab\xE2"), Ok(2));
+        assert_eq!(validator.pending(), 1);
+        let error = validator.feed(b"A").unwrap_err();
+        assert_eq!((error.valid_up_to(), error.error_len()), (2, Some(1)));
+    }
+
+    #[test]
+    fn surrogate_is_rejected() {
+        let mut validator = Utf8Validator::new();
+        assert_eq!(validator.feed(b"a\xED"), Ok(1));
+        let error = validator.feed(b"\xA0\x80").unwrap_err();
+        assert_eq!((error.valid_up_to(), error.error_len()), (1, Some(1)));
+    }
+
+    #[test]
+    fn finish_mid_sequence_is_incomplete() {
+        let mut validator = Utf8Validator::new();
+        assert_eq!(validator.feed(b"ok \xF0\x9F\xA6"), Ok(3));
+        let error = validator.finish().unwrap_err();
+        assert_eq!((error.valid_up_to(), error.error_len()), (3, None));
+    }
+
+    #[test]
+    fn error_is_sticky() {
+        let mut validator = Utf8Validator::new();
+        let error = validator.feed(b"x\xFFy").unwrap_err();
+        assert_eq!((error.valid_up_to(), error.error_len()), (1, Some(1)));
+        assert_eq!(validator.feed(b"valid"), Err(error));
+        assert_eq!(validator.finish(), Err(error));
+        assert_eq!(validator.valid_up_to(), 1);
+    }
+
+    #[test]
+    fn fixed_cases_match_std() {
+        let cases: [&[u8]; 11] = [
+            b"",
+            "héllo €🦀".as_bytes(),
+            b"ab\xE2\x82",
+            b"\xE2\x82A",
+            b"\xFF",
+            b"a\xED\xA0\x80",
+            b"\xF0\x9F\xA6",
+            b"\xC0\xAF",
+            b"x\xF4\x90\x80\x80",
+            b"\xE2\x28\xA1",
+            b"\xF0\x9F\xA6\x80\xF0\x9F",
+        ];
+        for case in cases {
+            assert_matches_std(case);
+        }
+    }
+
+    #[test]
+    fn random_inputs_match_std() {
+        // Mostly fragments of valid multi-byte sequences, with some random bytes.
+        const PIECES: [u8; 10] = [0xE2, 0x82, 0xAC, 0x41, 0xF0, 0x9F, 0xA6, 0x80, 0xC3, 0xA9];
+        let mut state = 0x2545_F491u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        for _ in 0..20_000 {
+            let len = (next() % 12) as usize;
+            let input: Vec<u8> = (0..len)
+                .map(|_| {
+                    let r = next();
+                    if r % 3 == 0 {
+                        (r >> 8) as u8
+                    } else {
+                        PIECES[(r >> 9) as usize % PIECES.len()]
+                    }
+                })
+                .collect();
+            assert_matches_std(&input);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::fmt;
+
+/// Longest possible LEB128 encoding of a `u64`.
+pub const MAX_VARINT_LEN: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarintError {
+    Truncated,
+    Overflow,
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarintError::Truncated => write!(f, "input ended in the middle of a varint"),
+            VarintError::Overflow => write!(f, "varint does not fit in 64 bits"),
+        }
+    }
+}
+
+impl std::error::Error for VarintError {}
+
+pub fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Reads one varint from the front of `buf` and advances past it.
+/// On error `buf` is left untouched.
+pub fn decode_varint(buf: &mut &[u8]) -> Result<u64, VarintError> {
+    let mut value = 0u64;
+    for (i, &byte) in buf.iter().enumerate() {
+        if i == MAX_VARINT_LEN - 1 && byte > 0x01 {
+            return Err(VarintError::Overflow);
+        }
+        value |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            *buf = &buf[i + 1..];
+            return Ok(value);
+        }
+    }
+    Err(VarintError::Truncated)
+}
+
+pub fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+pub fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+pub fn encode_signed_varint(value: i64, buf: &mut Vec<u8>) {
+    encode_varint(zigzag_encode(value), buf);
+}
+
+pub fn decode_signed_varint(buf: &mut &[u8]) -> Result<i64, VarintError> {
+    decode_varint(buf).map(zigzag_decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(value: u64) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_varint(value, &mut buf);
+        buf
+    }
+
+    #[test]
+    fn unsigned_round_trips() {
+        let cases: [(u64, usize); 8] = [
+            (0, 1),
+            (1, 1),
+            (127, 1),
+            (128, 2),
+            (16383, 2),
+            (16384, 3),
+            (u64::MAX - 1, MAX_VARINT_LEN),
+            (u64::MAX, MAX_VARINT_LEN),
+        ];
+        for (value, len) in cases {
+            let buf = encoded(value);
+            assert_eq!(buf.len(), len, "length of {value}");
+            let mut input = buf.as_slice();
+            assert_eq!(decode_varint(&mut input), Ok(value));
+            assert!(input.is_empty());
+        }
+    }
+
+    #[test]
+    fn known_encodings() {
+        assert_eq!(encoded(0), [0x00]);
+        assert_eq!(encoded(127), [0x7F]);
+        assert_eq!(encoded(128), [0x80, 0x01]);
+        assert_eq!(encoded(300), [0xAC, 0x02]);
+        assert_eq!(encoded(16384), [0x80, 0x80, 0x01]);
+        assert_eq!(
+            encoded(u64::MAX),
+            [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]
+        );
+    }
+
+    #[test]
+    fn signed_round_trips() {
+        for value in [0, 1, -1, 63, -64, 64, -65, i64::MAX, i64::MIN] {
+            let mut buf = Vec::new();
+            encode_signed_varint(value, &mut buf);
+            let mut input = buf.as_slice();
+            assert_eq!(decode_signed_varint(&mut input), Ok(value));
+            assert!(input.is_empty());
+        }
+    }
+
+    #[test]
+    fn zigzag_boundaries() {
+        let cases = [
+            (0, 0),
+            (-1, 1),
+            (1, 2),
+            (-2, 3),
+            (i64::MAX, u64::MAX - 1),
+            (i64::MIN, u64::MAX),
+        ];
+        for (signed, unsigned) in cases {
+            assert_eq!(zigzag_encode(signed), unsigned);
+            assert_eq!(zigzag_decode(unsigned), signed);
+        }
+        let mut buf = Vec::new();
+        encode_signed_varint(-64, &mut buf);
+        assert_eq!(buf, [0x7F]);
+    }
+
+    #[test]
+    fn consecutive_values_advance_cursor() {
+        let mut buf = Vec::new();
+        for value in [5, 300, u64::MAX] {
+            encode_varint(value, &mut buf);
+        }
+        let mut input = buf.as_slice();
+        assert_eq!(decode_varint(&mut input), Ok(5));
+        assert_eq!(decode_varint(&mut input), Ok(300));
+        assert_eq!(decode_varint(&mut input), Ok(u64::MAX));
+        assert_eq!(decode_varint(&mut input), Err(VarintError::Truncated));
+    }
+
+    #[test]
+    fn every_prefix_is_truncated_and_leaves_cursor() {
+        for value in [128, 16384, u64::MAX] {
+            let buf = encoded(value);
+            for end in 0..buf.len() {
+                let prefix = &buf[..end];
+                let mut input = prefix;
+                assert_eq!(decode_varint(&mut input), Err(VarintError::Truncated));
+                assert_eq!(input, prefix);
+            }
+        }
+    }
+
+    #[test]
+    fn overflowing_tenth_byte() {
+        for last in [0x02, 0x7F] {
+            let mut buf = [0xFF; MAX_VARINT_LEN];
+            buf[MAX_VARINT_LEN - 1] = last;
+            let mut input = buf.as_slice();
+            assert_eq!(decode_varint(&mut input), Err(VarintError::Overflow));
+            assert_eq!(input, buf);
+        }
+    }
+
+    #[test]
+    fn eleven_byte_input_overflows() {
+        let mut buf = [0xFF; MAX_VARINT_LEN + 1];
+        buf[MAX_VARINT_LEN] = 0x01;
+        let mut input = buf.as_slice();
+        assert_eq!(decode_varint(&mut input), Err(VarintError::Overflow));
+        assert_eq!(input, buf);
+
+        let mut padded = [0x80; MAX_VARINT_LEN + 1];
+        padded[MAX_VARINT_LEN] = 0x00;
+        assert_eq!(
+            decode_varint(&mut padded.as_slice()),
+            Err(VarintError::Overflow)
+        );
+    }
+}
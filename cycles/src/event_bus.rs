@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::time::Duration;
+
+struct Mailbox<T> {
+    queue: Mutex<VecDeque<T>>,
+    ready: Condvar,
+    capacity: usize,
+    dropped: AtomicU64,
+}
+
+impl<T> Mailbox<T> {
+    fn deliver(&self, event: T) {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.len() == self.capacity {
+            queue.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        queue.push_back(event);
+        self.ready.notify_one();
+    }
+}
+
+struct Shared<T> {
+    subscribers: Mutex<Vec<(u64, Weak<Mailbox<T>>)>>,
+    next_id: AtomicU64,
+}
+
+/// Fan-out publisher. The bus only holds `Weak` references to subscriber
+/// mailboxes, so dropping a [`Subscription`] is enough to stop receiving.
+pub struct EventBus<T> {
+    shared: Arc<Shared<T>>,
+    capacity: usize,
+}
+
+impl<T: Clone> EventBus<T> {
+    /// `capacity` bounds each subscriber's queue; when full the oldest event is dropped.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "queue capacity must be non-zero");
+        Self {
+            shared: Arc::new(Shared {
+                subscribers: Mutex::new(Vec::new()),
+                next_id: AtomicU64::new(0),
+            }),
+            capacity,
+        }
+    }
+
+    pub fn subscribe(&self) -> Subscription<T> {
+        let mailbox = Arc::new(Mailbox {
+            queue: Mutex::new(VecDeque::with_capacity(self.capacity)),
+            ready: Condvar::new(),
+            capacity: self.capacity,
+            dropped: AtomicU64::new(0),
+        });
+        let id = self.shared.next_id.fetch_add(1, Ordering::Relaxed);
+        self.shared
+            .subscribers
+            .lock()
+            .unwrap()
+            .push((id, Arc::downgrade(&mailbox)));
+        Subscription {
+            id,
+            mailbox,
+            bus: Arc::downgrade(&self.shared),
+        }
+    }
+
+    /// Returns how many subscribers received the event.
+    pub fn publish(&self, event: T) -> usize {
+        let live: Vec<_> = {
+            let mut subscribers = self.shared.subscribers.lock().unwrap();
+            subscribers.retain(|(_, mailbox)| mailbox.strong_count() > 0);
+            subscribers
+                .iter()
+                .filter_map(|(_, mailbox)| mailbox.upgrade())
+                .collect()
+        };
+        for mailbox in &live {
+            mailbox.deliver(event.clone());
+        }
+        live.len()
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        let mut subscribers = self.shared.subscribers.lock().unwrap();
+        subscribers.retain(|(_, mailbox)| mailbox.strong_count() > 0);
+        subscribers.len()
+    }
+}
+
+impl<T> Clone for EventBus<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+/// Receiving end of a subscription. Unsubscribes on drop.
+pub struct Subscription<T> {
+    id: u64,
+    mailbox: Arc<Mailbox<T>>,
+    bus: Weak<Shared<T>>,
+}
+
+impl<T> Subscription<T> {
+    pub fn try_recv(&self) -> Option<T> {
+        self.mailbox.queue.lock().unwrap().pop_front()
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<T> {
+        let queue = self.mailbox.queue.lock().unwrap();
+        let (mut queue, _) = self
+            .mailbox
+            .ready
+            .wait_timeout_while(queue, timeout, |queue| queue.is_empty())
+            .unwrap();
+        queue.pop_front()
+    }
+
+    pub fn drain(&self) -> Vec<T> {
+        self.mailbox.queue.lock().unwrap().drain(..).collect()
+    }
+
+    /// Number of events discarded because this subscriber's queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.mailbox.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn unsubscribe(self) {}
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        if let Some(shared) = self.bus.upgrade() {
+            shared
+                .subscribers
+                .lock()
+                .unwrap()
+                .retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn delivers_to_every_subscriber() {
+        let bus = EventBus::new(4);
+        let first = bus.subscribe();
+        let second = bus.subscribe();
+        assert_eq!(bus.publish(1), 2);
+        assert_eq!(first.try_recv(), Some(1));
+        assert_eq!(second.try_recv(), Some(1));
+        assert_eq!(first.try_recv(), None);
+    }
+
+    #[test]
+    fn full_queue_drops_oldest_and_counts() {
+        let bus = EventBus::new(2);
+        let subscription = bus.subscribe();
+        for i in 0..5 {
+            bus.publish(i);
+        }
+        assert_eq!(subscription.drain(), vec![3, 4]);
+        assert_eq!(subscription.dropped(), 3);
+    }
+
+    #[test]
+    fn drop_unsubscribes() {
+        let bus = EventBus::new(1);
+        let kept = bus.subscribe();
+        let dropped = bus.subscribe();
+        assert_eq!(bus.subscriber_count(), 2);
+        drop(dropped);
+        assert_eq!(bus.subscriber_count(), 1);
+        kept.unsubscribe();
+        assert_eq!(bus.subscriber_count(), 0);
+        assert_eq!(bus.publish(1), 0);
+    }
+
+    #[test]
+    fn subscription_outlives_bus() {
+        let bus = EventBus::new(1);
+        let subscription = bus.subscribe();
+        bus.publish("last");
+        drop(bus);
+        assert_eq!(subscription.try_recv(), Some("last"));
+    }
+
+    #[test]
+    fn clones_share_subscribers() {
+        let bus = EventBus::new(1);
+        let subscription = bus.subscribe();
+        let publisher = bus.clone();
+        thread::spawn(move || publisher.publish(7)).join().unwrap();
+        assert_eq!(subscription.try_recv(), Some(7));
+    }
+
+    #[test]
+    fn recv_timeout_waits_for_publish() {
+        let bus = EventBus::new(1);
+        let subscription = bus.subscribe();
+        assert_eq!(subscription.recv_timeout(Duration::from_millis(10)), None);
+        let waiter = thread::spawn(move || subscription.recv_timeout(Duration::from_secs(5)));
+        thread::sleep(Duration::from_millis(20));
+        bus.publish(42);
+        assert_eq!(waiter.join().unwrap(), Some(42));
+    }
+}
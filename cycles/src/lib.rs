@@ -0,0 +1,5 @@
+mod event_bus;
+mod weak_cache;
+
+pub use event_bus::{EventBus, Subscription};
+pub use weak_cache::Cache;
@@ -0,0 +1,48 @@
+use std::thread;
+use std::time::Duration;
+
+use cycles::{Cache, EventBus};
+
+fn main() {
+    let bus = EventBus::new(2);
+    let first = bus.subscribe();
+    let second = bus.subscribe();
+
+    let publisher = bus.clone();
+    thread::spawn(move || {
+        for i in 0..3 {
+            println!("delivered {i} to {}", publisher.publish(i));
+        }
+    })
+    .join()
+    .unwrap();
+
+    println!("first: {:?}, dropped {}", first.drain(), first.dropped());
+    second.unsubscribe();
+    println!("subscribers left: {}", bus.subscriber_count());
+
+    let waiter = thread::spawn(move || first.recv_timeout(Duration::from_secs(1)));
+    thread::sleep(Duration::from_millis(50));
+    bus.publish(42);
+    println!("waited for: {:?}", waiter.join().unwrap());
+    println!("subscribers left: {}", bus.subscriber_count());
+
+    let mut thumbnails = Cache::new(2);
+    let held = thumbnails.insert("cat@320", vec![0u8; 320]);
+    thumbnails.insert("cat@640", vec![0u8; 640]);
+    thumbnails.insert("cat@1280", vec![0u8; 1280]);
+    println!("live {}, pinned {}", thumbnails.len(), thumbnails.pinned());
+    println!(
+        "cat@320 evicted but held: {}",
+        thumbnails.get(&"cat@320").is_some()
+    );
+    thumbnails.insert("cat@2560", vec![0u8; 2560]);
+    thumbnails.insert("cat@5120", vec![0u8; 5120]);
+    drop(held);
+    thumbnails.purge();
+    println!(
+        "cat@320 after drop: {}",
+        thumbnails.get(&"cat@320").is_some()
+    );
+    println!("live {}, pinned {}", thumbnails.len(), thumbnails.pinned());
+}
@@ -0,0 +1,237 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::{Arc, Weak};
+
+/// Smallest number of index entries before `insert` bothers purging.
+const MIN_PURGE_THRESHOLD: usize = 16;
+
+struct Entry<V> {
+    value: Weak<V>,
+    /// Position in the LRU, if the cache is currently pinning this value.
+    generation: Option<u64>,
+}
+
+/// Cache that indexes values by `Weak` reference and keeps only the
+/// `capacity` most recently used values alive itself.
+///
+/// A value evicted from the LRU stays reachable through the cache for as
+/// long as some other `Arc` to it exists. Once the last one is dropped,
+/// `get` stops returning it, and its index entry is removed by the next
+/// `get` of that key, by [`Cache::purge`], or by the amortized purge `insert`
+/// runs whenever the index has doubled since the last one.
+///
+/// Lookups and LRU updates cost O(log capacity) on top of the hash lookup.
+pub struct Cache<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    recent: BTreeMap<u64, (K, Arc<V>)>,
+    next_generation: u64,
+    capacity: usize,
+    purge_threshold: usize,
+}
+
+impl<K: Eq + Hash + Clone, V> Cache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            recent: BTreeMap::new(),
+            next_generation: 0,
+            capacity,
+            purge_threshold: Self::threshold_for(0, capacity),
+        }
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<Arc<V>> {
+        match self.entries.get(key)?.value.upgrade() {
+            Some(value) => {
+                self.touch(key, value.clone());
+                Some(value)
+            }
+            None => {
+                self.entries.remove(key);
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Arc<V> {
+        if self.entries.len() >= self.purge_threshold {
+            self.purge();
+        }
+        let value = Arc::new(value);
+        let old = self.entries.insert(
+            key.clone(),
+            Entry {
+                value: Arc::downgrade(&value),
+                generation: None,
+            },
+        );
+        if let Some(generation) = old.and_then(|entry| entry.generation) {
+            self.recent.remove(&generation);
+        }
+        self.touch(&key, value.clone());
+        value
+    }
+
+    pub fn get_or_insert_with(&mut self, key: K, make: impl FnOnce() -> V) -> Arc<V> {
+        match self.get(&key) {
+            Some(value) => value,
+            None => self.insert(key, make()),
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<Arc<V>> {
+        let entry = self.entries.remove(key)?;
+        let pinned = entry
+            .generation
+            .and_then(|generation| self.recent.remove(&generation));
+        match pinned {
+            Some((_, value)) => Some(value),
+            None => entry.value.upgrade(),
+        }
+    }
+
+    /// Drops index entries whose values are no longer alive anywhere.
+    pub fn purge(&mut self) {
+        self.entries
+            .retain(|_, entry| entry.value.strong_count() > 0);
+        self.purge_threshold = Self::threshold_for(self.entries.len(), self.capacity);
+    }
+
+    /// Number of entries whose values are still alive.
+    pub fn len(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|entry| entry.value.strong_count() > 0)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of values the cache itself is keeping alive.
+    pub fn pinned(&self) -> usize {
+        self.recent.len()
+    }
+
+    fn threshold_for(live: usize, capacity: usize) -> usize {
+        (2 * live.max(capacity)).max(MIN_PURGE_THRESHOLD)
+    }
+
+    fn touch(&mut self, key: &K, value: Arc<V>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let Some(entry) = self.entries.get_mut(key) else {
+            return;
+        };
+        if let Some(generation) = entry.generation.take() {
+            self.recent.remove(&generation);
+        }
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        entry.generation = Some(generation);
+        self.recent.insert(generation, (key.clone(), value));
+
+        if self.recent.len() > self.capacity {
+            if let Some((_, (evicted, _))) = self.recent.pop_first() {
+                if let Some(entry) = self.entries.get_mut(&evicted) {
+                    entry.generation = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_most_recent_values_pinned() {
+        let mut cache = Cache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("c", 3);
+        assert_eq!(cache.pinned(), 2);
+        assert!(cache.get(&"a").is_none());
+        assert_eq!(cache.get(&"b").as_deref(), Some(&2));
+        assert_eq!(cache.get(&"c").as_deref(), Some(&3));
+    }
+
+    #[test]
+    fn get_refreshes_lru_position() {
+        let mut cache = Cache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.get(&"a");
+        cache.insert("c", 3);
+        assert!(cache.get(&"a").is_some());
+        assert!(cache.get(&"b").is_none());
+    }
+
+    #[test]
+    fn evicted_value_stays_reachable_while_held() {
+        let mut cache = Cache::new(1);
+        let held = cache.insert("a", 1);
+        cache.insert("b", 2);
+        let again = cache.get(&"a").unwrap();
+        assert!(Arc::ptr_eq(&held, &again));
+
+        cache.insert("b", 2);
+        drop((held, again));
+        assert!(cache.get(&"a").is_none());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn reinsert_replaces_value() {
+        let mut cache = Cache::new(2);
+        cache.insert("a", 1);
+        cache.insert("a", 10);
+        assert_eq!(cache.pinned(), 1);
+        assert_eq!(cache.get(&"a").as_deref(), Some(&10));
+    }
+
+    #[test]
+    fn remove_returns_pinned_value() {
+        let mut cache = Cache::new(2);
+        cache.insert("a", 1);
+        assert_eq!(cache.remove(&"a").as_deref(), Some(&1));
+        assert_eq!(cache.pinned(), 0);
+        assert!(cache.get(&"a").is_none());
+    }
+
+    #[test]
+    fn zero_capacity_pins_nothing() {
+        let mut cache = Cache::new(0);
+        let held = cache.insert("a", 1);
+        assert_eq!(cache.pinned(), 0);
+        assert!(cache.get(&"a").is_some());
+        drop(held);
+        assert!(cache.get(&"a").is_none());
+    }
+
+    #[test]
+    fn get_or_insert_with_only_builds_on_miss() {
+        let mut cache = Cache::new(1);
+        let mut builds = 0;
+        for _ in 0..3 {
+            cache.get_or_insert_with("a", || {
+                builds += 1;
+                1
+            });
+        }
+        assert_eq!(builds, 1);
+    }
+
+    #[test]
+    fn insert_purges_dead_entries_for_distinct_keys() {
+        let mut cache = Cache::new(4);
+        for key in 0..10_000 {
+            cache.insert(key, key);
+        }
+        assert!(cache.entries.len() <= MIN_PURGE_THRESHOLD);
+        assert_eq!(cache.len(), 4);
+    }
+}
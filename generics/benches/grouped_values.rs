@@ -0,0 +1,101 @@
+//! Measures the grouped maps for a skewed distribution: a few hot keys with
+//! many values, a long tail with one or two.
+//!
+//! The per-key storage is picked at compile time, so compare the two runs:
+//!
+//! ```text
+//! cargo bench -p generics
+//! cargo bench -p generics --features small-vec
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use generics::{GroupedHashMap, GroupedMap, GroupedOrderedMap};
+
+struct CountingAlloc;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const VALUES: usize = 200_000;
+const KEYS: f64 = 100_000.0;
+const RUNS: usize = 5;
+
+#[cfg(not(feature = "small-vec"))]
+const STORAGE: &str = "Vec";
+#[cfg(feature = "small-vec")]
+const STORAGE: &str = "SmallVec";
+
+/// Deterministic xorshift keys, cubed so low key ids are much hotter.
+fn skewed_keys() -> Vec<u32> {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    (0..VALUES)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let unit = (state >> 11) as f64 / (1u64 << 53) as f64;
+            (KEYS * unit * unit * unit) as u32
+        })
+        .collect()
+}
+
+fn run<M: GroupedMap<u32, u64> + Default>(name: &str, keys: &[u32]) {
+    let mut insert = Duration::MAX;
+    let mut iterate = Duration::MAX;
+    let mut heap = 0;
+    for _ in 0..RUNS {
+        let before = LIVE_BYTES.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let mut map = M::default();
+        for (i, key) in keys.iter().enumerate() {
+            map.insert(*key, i as u64);
+        }
+        let map = black_box(map);
+        insert = insert.min(start.elapsed());
+        heap = LIVE_BYTES.load(Ordering::Relaxed) - before;
+
+        let start = Instant::now();
+        let sum: u64 = map.iter().flat_map(|(_, values)| values).sum();
+        black_box(sum);
+        iterate = iterate.min(start.elapsed());
+    }
+    println!(
+        "{name:>18}: insert {insert:>10.2?}  iterate {iterate:>10.2?}  heap {:>6} KiB",
+        heap / 1024
+    );
+}
+
+fn main() {
+    let keys = skewed_keys();
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+    for key in &keys {
+        *counts.entry(*key).or_default() += 1;
+    }
+    let small = counts.values().filter(|&&n| n <= 2).count();
+    println!(
+        "{VALUES} values over {} keys, {small} keys hold at most 2 values, storage: {STORAGE}",
+        counts.len()
+    );
+
+    run::<GroupedHashMap<u32, u64>>("GroupedHashMap", &keys);
+    run::<GroupedOrderedMap<u32, u64>>("GroupedOrderedMap", &keys);
+}
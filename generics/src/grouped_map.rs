@@ -0,0 +1,230 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::ops::RangeBounds;
+
+/// A map where every key holds a group of values.
+pub trait GroupedMap<K, V> {
+    type Iter<'a>: Iterator<Item = (&'a K, &'a [V])>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, key: K, value: V);
+    fn get(&self, key: &K) -> Option<&[V]>;
+    fn remove(&mut self, key: &K) -> Option<Vec<V>>;
+    fn len(&self) -> usize;
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn value_count(&self) -> usize {
+        self.iter().map(|(_, values)| values.len()).sum()
+    }
+}
+
+/// Per-key storage. With the `small-vec` feature the first few values of
+/// each key are kept inline instead of in a separate heap allocation.
+#[cfg(not(feature = "small-vec"))]
+pub(crate) type Group<V> = Vec<V>;
+#[cfg(feature = "small-vec")]
+pub(crate) type Group<V> = crate::SmallVec<V, { crate::INLINE_VALUES }>;
+
+#[cfg(not(feature = "small-vec"))]
+pub(crate) fn into_vec<V>(group: Group<V>) -> Vec<V> {
+    group
+}
+#[cfg(feature = "small-vec")]
+pub(crate) fn into_vec<V>(group: Group<V>) -> Vec<V> {
+    group.into_vec()
+}
+
+type HashIter<'a, K, V> = std::iter::Map<
+    std::collections::hash_map::Iter<'a, K, Group<V>>,
+    fn((&'a K, &'a Group<V>)) -> (&'a K, &'a [V]),
+>;
+
+type OrderedIter<'a, K, V> = std::iter::Map<
+    std::collections::btree_map::Iter<'a, K, Group<V>>,
+    fn((&'a K, &'a Group<V>)) -> (&'a K, &'a [V]),
+>;
+
+fn with_slice<'a, K, V>((key, values): (&'a K, &'a Group<V>)) -> (&'a K, &'a [V]) {
+    (key, values.as_slice())
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupedHashMap<K, V> {
+    pub(crate) inner: HashMap<K, Group<V>>,
+}
+
+impl<K: Eq + Hash, V> GroupedHashMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            inner: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> Default for GroupedHashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V> GroupedMap<K, V> for GroupedHashMap<K, V> {
+    type Iter<'a>
+        = HashIter<'a, K, V>
+    where
+        Self: 'a;
+
+    fn insert(&mut self, key: K, value: V) {
+        self.inner.entry(key).or_default().push(value);
+    }
+
+    fn get(&self, key: &K) -> Option<&[V]> {
+        self.inner.get(key).map(Group::as_slice)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<Vec<V>> {
+        self.inner.remove(key).map(into_vec)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.inner.iter().map(with_slice)
+    }
+}
+
+/// Same as [`GroupedHashMap`], but keys are kept sorted so they can be queried by range.
+#[derive(Debug, Clone)]
+pub struct GroupedOrderedMap<K, V> {
+    pub(crate) inner: BTreeMap<K, Group<V>>,
+}
+
+impl<K: Ord, V> GroupedOrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            inner: BTreeMap::new(),
+        }
+    }
+
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> impl Iterator<Item = (&K, &[V])> {
+        self.inner.range(range).map(with_slice)
+    }
+
+    pub fn first_key(&self) -> Option<&K> {
+        self.inner.keys().next()
+    }
+
+    pub fn last_key(&self) -> Option<&K> {
+        self.inner.keys().next_back()
+    }
+}
+
+impl<K: Ord, V> Default for GroupedOrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> GroupedMap<K, V> for GroupedOrderedMap<K, V> {
+    type Iter<'a>
+        = OrderedIter<'a, K, V>
+    where
+        Self: 'a;
+
+    fn insert(&mut self, key: K, value: V) {
+        self.inner.entry(key).or_default().push(value);
+    }
+
+    fn get(&self, key: &K) -> Option<&[V]> {
+        self.inner.get(key).map(Group::as_slice)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<Vec<V>> {
+        self.inner.remove(key).map(into_vec)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.inner.iter().map(with_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Behaviour every backing store must share.
+    fn check_grouping<M: GroupedMap<&'static str, u32> + Default>() {
+        let mut map = M::default();
+        assert!(map.is_empty());
+        assert_eq!(map.get(&"a"), None);
+
+        for (key, value) in [("a", 1), ("b", 2), ("a", 3), ("c", 4), ("a", 5)] {
+            map.insert(key, value);
+        }
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.value_count(), 5);
+        assert_eq!(map.get(&"a"), Some(&[1, 3, 5][..]));
+        assert!(map.contains_key(&"b"));
+        assert!(!map.contains_key(&"z"));
+
+        let mut groups: Vec<_> = map.iter().collect();
+        groups.sort();
+        assert_eq!(
+            groups,
+            [(&"a", &[1, 3, 5][..]), (&"b", &[2][..]), (&"c", &[4][..])]
+        );
+
+        assert_eq!(map.remove(&"a"), Some(vec![1, 3, 5]));
+        assert_eq!(map.remove(&"a"), None);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.value_count(), 2);
+    }
+
+    #[test]
+    fn hash_map_groups_values() {
+        check_grouping::<GroupedHashMap<_, _>>();
+    }
+
+    #[test]
+    fn ordered_map_groups_values() {
+        check_grouping::<GroupedOrderedMap<_, _>>();
+    }
+
+    #[test]
+    fn ordered_map_iterates_in_key_order() {
+        let mut map = GroupedOrderedMap::new();
+        for key in [5, 1, 4, 2, 1] {
+            map.insert(key, key * 10);
+        }
+        let keys: Vec<_> = map.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, [1, 2, 4, 5]);
+        assert_eq!((map.first_key(), map.last_key()), (Some(&1), Some(&5)));
+
+        let range: Vec<_> = map.range(2..=4).collect();
+        assert_eq!(range, [(&2, &[20][..]), (&4, &[40][..])]);
+        assert_eq!(map.range(..2).next(), Some((&1, &[10, 10][..])));
+        assert_eq!(map.range(6..).next(), None);
+    }
+
+    #[test]
+    fn ordered_map_empty_bounds() {
+        let map = GroupedOrderedMap::<u8, u8>::new();
+        assert_eq!((map.first_key(), map.last_key()), (None, None));
+    }
+}
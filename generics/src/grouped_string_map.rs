@@ -0,0 +1,163 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grouped_map::into_vec;
+use crate::{GroupedHashMap, GroupedOrderedMap};
+
+/// Object-safe view of a grouped map keyed by `String`, for use as
+/// `Box<dyn GroupedStringMap<V>>` when the backing store is picked at runtime.
+pub trait GroupedStringMap<V = String>: Send + Sync {
+    fn insert(&mut self, key: String, value: V);
+    fn get(&self, key: &str) -> Option<&[V]>;
+    fn remove(&mut self, key: &str) -> Option<Vec<V>>;
+    fn len(&self) -> usize;
+    fn iter(&self) -> Box<dyn Iterator<Item = (&str, &[V])> + '_>;
+
+    fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Both maps keep their groups in `inner`, keyed by `String`, which can be
+/// looked up by `&str` directly.
+macro_rules! impl_grouped_string_map {
+    ($map:ident) => {
+        impl<V: Send + Sync> GroupedStringMap<V> for $map<String, V> {
+            fn insert(&mut self, key: String, value: V) {
+                self.inner.entry(key).or_default().push(value);
+            }
+
+            fn get(&self, key: &str) -> Option<&[V]> {
+                self.inner.get(key).map(|values| values.as_slice())
+            }
+
+            fn remove(&mut self, key: &str) -> Option<Vec<V>> {
+                self.inner.remove(key).map(into_vec)
+            }
+
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+
+            fn iter(&self) -> Box<dyn Iterator<Item = (&str, &[V])> + '_> {
+                Box::new(self.inner.iter().map(|(k, v)| (k.as_str(), v.as_slice())))
+            }
+        }
+    };
+}
+
+impl_grouped_string_map!(GroupedHashMap);
+impl_grouped_string_map!(GroupedOrderedMap);
+
+/// Which backing store to build, e.g. parsed from a config value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupedMapKind {
+    #[default]
+    Hash,
+    Ordered,
+}
+
+impl GroupedMapKind {
+    pub fn build<V: Send + Sync + 'static>(self) -> Box<dyn GroupedStringMap<V>> {
+        match self {
+            GroupedMapKind::Hash => Box::new(GroupedHashMap::<String, V>::new()),
+            GroupedMapKind::Ordered => Box::new(GroupedOrderedMap::<String, V>::new()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGroupedMapKind(pub String);
+
+impl fmt::Display for UnknownGroupedMapKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown grouped map kind {:?}, expected \"hash\" or \"ordered\"",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownGroupedMapKind {}
+
+/// Accepts `"hash"` or `"ordered"`, ignoring case and surrounding whitespace.
+impl FromStr for GroupedMapKind {
+    type Err = UnknownGroupedMapKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "hash" => Ok(GroupedMapKind::Hash),
+            "ordered" => Ok(GroupedMapKind::Ordered),
+            _ => Err(UnknownGroupedMapKind(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_kind_ignoring_case_and_whitespace() {
+        assert_eq!("hash".parse(), Ok(GroupedMapKind::Hash));
+        assert_eq!(" Ordered\n".parse(), Ok(GroupedMapKind::Ordered));
+        assert_eq!("HASH".parse(), Ok(GroupedMapKind::Hash));
+    }
+
+    #[test]
+    fn rejects_unknown_kind_with_original_text() {
+        for input in ["btree", "", " heap "] {
+            let error = input.parse::<GroupedMapKind>().unwrap_err();
+            assert_eq!(error, UnknownGroupedMapKind(input.to_string()));
+        }
+        assert_eq!(
+            UnknownGroupedMapKind("heap".to_string()).to_string(),
+            "unknown grouped map kind \"heap\", expected \"hash\" or \"ordered\""
+        );
+    }
+
+    fn fill(map: &mut dyn GroupedStringMap<u32>) {
+        for (key, value) in [("web", 1), ("db", 2), ("web", 3)] {
+            map.insert(key.to_string(), value);
+        }
+    }
+
+    #[test]
+    fn built_maps_work_through_trait_object() {
+        for kind in [GroupedMapKind::Hash, GroupedMapKind::Ordered] {
+            let mut map = kind.build::<u32>();
+            assert!(map.is_empty());
+            fill(map.as_mut());
+            assert_eq!(map.len(), 2);
+            assert_eq!(map.get("web"), Some(&[1, 3][..]));
+            assert!(map.contains_key("db"));
+            assert!(!map.contains_key("cache"));
+
+            let mut groups: Vec<_> = map.iter().collect();
+            groups.sort();
+            assert_eq!(groups, [("db", &[2][..]), ("web", &[1, 3][..])]);
+
+            assert_eq!(map.remove("web"), Some(vec![1, 3]));
+            assert_eq!(map.remove("web"), None);
+            assert_eq!(map.len(), 1);
+        }
+    }
+
+    #[test]
+    fn ordered_kind_iterates_sorted() {
+        let mut map = GroupedMapKind::Ordered.build::<u32>();
+        fill(map.as_mut());
+        let keys: Vec<_> = map.iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, ["db", "web"]);
+    }
+
+    #[test]
+    fn default_kind_is_hash() {
+        assert_eq!(GroupedMapKind::default(), GroupedMapKind::Hash);
+    }
+}
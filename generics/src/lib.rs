@@ -0,0 +1,15 @@
+mod grouped_map;
+mod grouped_string_map;
+#[cfg(feature = "small-vec")]
+mod small_vec;
+mod typed_key_map;
+
+pub use grouped_map::{GroupedHashMap, GroupedMap, GroupedOrderedMap};
+pub use grouped_string_map::{GroupedMapKind, GroupedStringMap, UnknownGroupedMapKind};
+#[cfg(feature = "small-vec")]
+pub use small_vec::SmallVec;
+pub use typed_key_map::{Key, TypedKeyMap};
+
+/// Values kept inline per key before spilling to the heap, with `small-vec`.
+#[cfg(feature = "small-vec")]
+pub const INLINE_VALUES: usize = 2;
@@ -0,0 +1,68 @@
+use generics::{
+    GroupedHashMap, GroupedMap, GroupedMapKind, GroupedOrderedMap, GroupedStringMap, Key,
+    TypedKeyMap,
+};
+
+const REQUEST_ID: Key<u64> = Key::new("request_id");
+const USER: Key<String> = Key::new("user");
+
+fn fill<M: GroupedMap<u32, &'static str>>(map: &mut M) {
+    map.insert(3, "three");
+    map.insert(1, "one");
+    map.insert(2, "two");
+    map.insert(1, "uno");
+    map.insert(5, "five");
+}
+
+fn summary<M: GroupedMap<u32, &'static str>>(map: &M) {
+    println!("{} keys, {} values", map.len(), map.value_count());
+}
+
+fn main() {
+    let mut hashed = GroupedHashMap::new();
+    fill(&mut hashed);
+    summary(&hashed);
+    println!("{:?}", hashed.get(&1));
+
+    let mut ordered = GroupedOrderedMap::new();
+    fill(&mut ordered);
+    summary(&ordered);
+    println!(
+        "first: {:?}, last: {:?}",
+        ordered.first_key(),
+        ordered.last_key()
+    );
+    for (key, values) in ordered.range(2..=3) {
+        println!("{key}: {values:?}");
+    }
+
+    let mut extensions = TypedKeyMap::new();
+    extensions.insert(REQUEST_ID, 7);
+    extensions.insert(USER, "herbert".to_string());
+    if let Some(id) = extensions.get_mut(REQUEST_ID) {
+        *id += 1;
+    }
+    println!("{extensions:?}");
+    println!(
+        "{:?} {:?}",
+        extensions.get(REQUEST_ID),
+        extensions.get(USER)
+    );
+    println!("{:?}", extensions.get(Key::<u32>::new("request_id")));
+
+    for kind in ["hash", "ordered", "heap"] {
+        let kind = match kind.parse::<GroupedMapKind>() {
+            Ok(kind) => kind,
+            Err(e) => {
+                println!("{e}");
+                continue;
+            }
+        };
+        let mut tags: Box<dyn GroupedStringMap> = kind.build();
+        tags.insert("web".to_string(), "server-1".to_string());
+        tags.insert("db".to_string(), "server-2".to_string());
+        tags.insert("web".to_string(), "server-3".to_string());
+        let keys: Vec<&str> = tags.iter().map(|(key, _)| key).collect();
+        println!("{kind:?}: {keys:?}, web = {:?}", tags.get("web"));
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt;
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+use std::slice;
+
+/// A vector that stores up to `N` items inline and only moves to the heap
+/// when it outgrows that.
+pub struct SmallVec<T, const N: usize>(Repr<T, N>);
+
+// Private so that `len` can only change through methods that keep the first
+// `len` items initialized.
+enum Repr<T, const N: usize> {
+    Inline {
+        len: usize,
+        items: [MaybeUninit<T>; N],
+    },
+    Heap(Vec<T>),
+}
+
+impl<T, const N: usize> SmallVec<T, N> {
+    pub fn new() -> Self {
+        SmallVec(Repr::Inline {
+            len: 0,
+            items: [const { MaybeUninit::uninit() }; N],
+        })
+    }
+
+    pub fn push(&mut self, value: T) {
+        match &mut self.0 {
+            Repr::Inline { len, items } if *len < N => {
+                items[*len].write(value);
+                *len += 1;
+            }
+            Repr::Inline { .. } => {
+                let mut spilled = Vec::with_capacity(N * 2);
+                spilled.extend(mem::take(self).into_vec());
+                spilled.push(value);
+                self.0 = Repr::Heap(spilled);
+            }
+            Repr::Heap(vec) => vec.push(value),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn spilled(&self) -> bool {
+        matches!(self.0, Repr::Heap(_))
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        match &self.0 {
+            // SAFETY: the first `len` items are always initialized.
+            Repr::Inline { len, items } => unsafe {
+                slice::from_raw_parts(items.as_ptr().cast::<T>(), *len)
+            },
+            Repr::Heap(vec) => vec,
+        }
+    }
+
+    pub fn into_vec(mut self) -> Vec<T> {
+        match &mut self.0 {
+            Repr::Inline { len, items } => {
+                let count = mem::replace(len, 0);
+                // SAFETY: the first `count` items are initialized, and `len` is
+                // zeroed first so `Drop` will not touch them again.
+                (0..count)
+                    .map(|i| unsafe { items[i].assume_init_read() })
+                    .collect()
+            }
+            Repr::Heap(vec) => mem::take(vec),
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for SmallVec<T, N> {
+    fn drop(&mut self) {
+        if let Repr::Inline { len, items } = &mut self.0 {
+            // SAFETY: the first `len` items are initialized and dropped exactly once here.
+            unsafe {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                    items.as_mut_ptr().cast::<T>(),
+                    *len,
+                ));
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for SmallVec<T, N> {
+    fn clone(&self) -> Self {
+        let mut cloned = Self::new();
+        for value in self.as_slice() {
+            cloned.push(value.clone());
+        }
+        cloned
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> From<SmallVec<T, N>> for Vec<T> {
+    fn from(values: SmallVec<T, N>) -> Self {
+        values.into_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn push_stays_inline_up_to_n() {
+        let mut values: SmallVec<u32, 3> = SmallVec::new();
+        assert!(values.is_empty());
+        for i in 0..3 {
+            values.push(i);
+        }
+        assert!(!values.spilled());
+        assert_eq!(values.as_slice(), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn push_past_n_spills_to_heap() {
+        let mut values: SmallVec<String, 2> = SmallVec::new();
+        for i in 0..5 {
+            values.push(i.to_string());
+        }
+        assert!(values.spilled());
+        assert_eq!(values.len(), 5);
+        assert_eq!(values.as_slice(), &["0", "1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn into_vec_from_inline_and_heap() {
+        let mut inline: SmallVec<String, 4> = SmallVec::new();
+        inline.push("a".to_string());
+        inline.push("b".to_string());
+        assert_eq!(inline.into_vec(), vec!["a", "b"]);
+
+        let mut heap: SmallVec<String, 1> = SmallVec::new();
+        heap.push("a".to_string());
+        heap.push("b".to_string());
+        assert!(heap.spilled());
+        assert_eq!(Vec::from(heap), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        let mut values: SmallVec<String, 2> = SmallVec::new();
+        values.push("a".to_string());
+        let mut cloned = values.clone();
+        cloned.push("b".to_string());
+        cloned.push("c".to_string());
+        assert_eq!(values.as_slice(), &["a"]);
+        assert_eq!(cloned.as_slice(), &["a", "b", "c"]);
+        assert!(cloned.spilled());
+    }
+
+    #[test]
+    fn drops_each_value_exactly_once() {
+        let tracker = Rc::new(());
+        let count = || Rc::strong_count(&tracker) - 1;
+
+        {
+            let mut inline: SmallVec<Rc<()>, 3> = SmallVec::new();
+            inline.push(tracker.clone());
+            inline.push(tracker.clone());
+            assert_eq!(count(), 2);
+            let cloned = inline.clone();
+            assert_eq!(count(), 4);
+            drop(cloned);
+            assert_eq!(count(), 2);
+        }
+        assert_eq!(count(), 0);
+
+        {
+            let mut spilled: SmallVec<Rc<()>, 2> = SmallVec::new();
+            for _ in 0..3 {
+                spilled.push(tracker.clone());
+            }
+            assert!(spilled.spilled());
+            assert_eq!(count(), 3);
+        }
+        assert_eq!(count(), 0);
+
+        let mut moved: SmallVec<Rc<()>, 2> = SmallVec::new();
+        moved.push(tracker.clone());
+        let vec = moved.into_vec();
+        assert_eq!(count(), 1);
+        drop(vec);
+        assert_eq!(count(), 0);
+    }
+}
@@ -0,0 +1,147 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A named key that also fixes the type of the value stored under it.
+pub struct Key<T> {
+    name: &'static str,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> Key<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for Key<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Key<T> {}
+
+impl<T> fmt::Debug for Key<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Key<{}>({:?})", std::any::type_name::<T>(), self.name)
+    }
+}
+
+/// Heterogeneous map indexed by [`Key<T>`]. Entries are identified by key
+/// name *and* value type, so the same name can be reused for different types.
+#[derive(Default)]
+pub struct TypedKeyMap {
+    inner: HashMap<(TypeId, &'static str), Box<dyn Any + Send + Sync>>,
+}
+
+impl TypedKeyMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<T: Any + Send + Sync>(&mut self, key: Key<T>, value: T) -> Option<T> {
+        self.inner
+            .insert(Self::slot(key), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    pub fn get<T: Any>(&self, key: Key<T>) -> Option<&T> {
+        self.inner.get(&Self::slot(key))?.downcast_ref()
+    }
+
+    pub fn get_mut<T: Any>(&mut self, key: Key<T>) -> Option<&mut T> {
+        self.inner.get_mut(&Self::slot(key))?.downcast_mut()
+    }
+
+    pub fn remove<T: Any>(&mut self, key: Key<T>) -> Option<T> {
+        self.inner
+            .remove(&Self::slot(key))?
+            .downcast()
+            .ok()
+            .map(|value| *value)
+    }
+
+    pub fn contains_key<T: Any>(&self, key: Key<T>) -> bool {
+        self.inner.contains_key(&Self::slot(key))
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn slot<T: Any>(key: Key<T>) -> (TypeId, &'static str) {
+        (TypeId::of::<T>(), key.name)
+    }
+}
+
+impl fmt::Debug for TypedKeyMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.inner.keys().map(|(_, name)| name))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNT: Key<u32> = Key::new("count");
+    const COUNT_TEXT: Key<String> = Key::new("count");
+
+    #[test]
+    fn same_name_different_types_do_not_collide() {
+        let mut map = TypedKeyMap::new();
+        assert_eq!(map.insert(COUNT, 3), None);
+        assert_eq!(map.insert(COUNT_TEXT, "three".to_string()), None);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(COUNT), Some(&3));
+        assert_eq!(map.get(COUNT_TEXT).map(String::as_str), Some("three"));
+        assert_eq!(map.get(Key::<u64>::new("count")), None);
+    }
+
+    #[test]
+    fn insert_returns_previous_value() {
+        let mut map = TypedKeyMap::new();
+        assert_eq!(map.insert(COUNT, 1), None);
+        assert_eq!(map.insert(COUNT, 2), Some(1));
+        assert_eq!(map.get(COUNT), Some(&2));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn get_mut_updates_in_place() {
+        let mut map = TypedKeyMap::new();
+        assert_eq!(map.get_mut(COUNT), None);
+        map.insert(COUNT, 1);
+        *map.get_mut(COUNT).unwrap() += 41;
+        assert_eq!(map.get(COUNT), Some(&42));
+    }
+
+    #[test]
+    fn remove_only_takes_matching_type() {
+        let mut map = TypedKeyMap::new();
+        map.insert(COUNT, 7);
+        map.insert(COUNT_TEXT, "seven".to_string());
+        assert_eq!(map.remove(COUNT), Some(7));
+        assert_eq!(map.remove(COUNT), None);
+        assert!(!map.contains_key(COUNT));
+        assert!(map.contains_key(COUNT_TEXT));
+        assert_eq!(map.remove(COUNT_TEXT).as_deref(), Some("seven"));
+        assert!(map.is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+mod windowed_stats;
+
+pub use windowed_stats::{WindowStats, WindowedStats, WindowedStatsExt};
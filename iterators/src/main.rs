@@ -0,0 +1,12 @@
+use iterators::WindowedStatsExt;
+
+fn main() {
+    let samples = [1.0, 3.0, 2.0, 8.0, 4.0, 4.0, 1.0];
+    for stats in samples.into_iter().windowed_stats(3) {
+        println!("{stats:?}");
+    }
+
+    let ints: Vec<u32> = (1..=10).collect();
+    let last = ints.into_iter().windowed_stats(4).last();
+    println!("{last:?}");
+}
@@ -0,0 +1,299 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowStats {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub stddev: f64,
+}
+
+/// Count, mean, sum of squared deviations (M2), min and max of a run of values.
+#[derive(Debug, Clone, Copy)]
+struct Summary {
+    count: f64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Summary {
+    const EMPTY: Summary = Summary {
+        count: 0.0,
+        mean: 0.0,
+        m2: 0.0,
+        min: f64::INFINITY,
+        max: f64::NEG_INFINITY,
+    };
+
+    fn of(value: f64) -> Self {
+        Summary {
+            count: 1.0,
+            mean: value,
+            m2: 0.0,
+            min: value,
+            max: value,
+        }
+    }
+
+    /// Chan et al. pairwise combination; never subtracts a value back out.
+    fn combine(self, other: Summary) -> Summary {
+        if self.count == 0.0 {
+            return other;
+        }
+        if other.count == 0.0 {
+            return self;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        // `delta` overflows for finite means of opposite sign near f64::MAX;
+        // a weighted sum of the two means cannot.
+        let mean = if delta.is_finite() {
+            self.mean + delta * (other.count / count)
+        } else {
+            self.mean * (self.count / count) + other.mean * (other.count / count)
+        };
+        Summary {
+            count,
+            mean,
+            m2: self.m2 + other.m2 + delta * delta * (self.count * other.count / count),
+            min: nan_min(self.min, other.min),
+            max: nan_max(self.max, other.max),
+        }
+    }
+}
+
+fn nan_min(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        f64::NAN
+    } else {
+        a.min(b)
+    }
+}
+
+fn nan_max(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        f64::NAN
+    } else {
+        a.max(b)
+    }
+}
+
+/// Yields rolling statistics over the last `size` items once the window is full.
+///
+/// The window is a queue built from two stacks of partial summaries, so each
+/// step is amortized O(1) and values leaving the window are dropped rather
+/// than subtracted, which keeps large or non-finite values from skewing later
+/// windows.
+///
+/// While the window holds a NaN, every field is NaN. While it holds an
+/// infinity (and no NaN), `mean` and `stddev` are NaN and `min`/`max` report
+/// the infinity. Once such a value leaves the window it has no further effect.
+/// A window of finite values always has a finite `mean`; its `stddev` is
+/// infinite only if the variance itself overflows `f64`.
+pub struct WindowedStats<I> {
+    iter: I,
+    size: usize,
+    /// Older values, each entry summarizing itself and every newer entry below it.
+    front: Vec<Summary>,
+    /// Newer values, summarized together in `back_summary`.
+    back: Vec<f64>,
+    back_summary: Summary,
+}
+
+impl<I> WindowedStats<I> {
+    pub fn new(iter: I, size: usize) -> Self {
+        assert!(size > 0, "window size must be non-zero");
+        Self {
+            iter,
+            size,
+            front: Vec::with_capacity(size),
+            back: Vec::with_capacity(size),
+            back_summary: Summary::EMPTY,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    fn push(&mut self, value: f64) {
+        if self.len() == self.size {
+            self.pop_oldest();
+        }
+        self.back.push(value);
+        self.back_summary = self.back_summary.combine(Summary::of(value));
+    }
+
+    fn pop_oldest(&mut self) {
+        if self.front.is_empty() {
+            let mut summary = Summary::EMPTY;
+            for value in self.back.drain(..).rev() {
+                summary = Summary::of(value).combine(summary);
+                self.front.push(summary);
+            }
+            self.back_summary = Summary::EMPTY;
+        }
+        self.front.pop();
+    }
+
+    fn stats(&self) -> WindowStats {
+        let front = self.front.last().copied().unwrap_or(Summary::EMPTY);
+        let window = front.combine(self.back_summary);
+        let (mean, stddev) = if window.min.is_finite() && window.max.is_finite() {
+            (window.mean, (window.m2 / window.count).sqrt())
+        } else {
+            (f64::NAN, f64::NAN)
+        };
+        WindowStats {
+            mean,
+            min: window.min,
+            max: window.max,
+            stddev,
+        }
+    }
+}
+
+impl<I> Iterator for WindowedStats<I>
+where
+    I: Iterator,
+    I::Item: Into<f64>,
+{
+    type Item = WindowStats;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let value = self.iter.next()?.into();
+            self.push(value);
+            if self.len() == self.size {
+                return Some(self.stats());
+            }
+        }
+    }
+}
+
+pub trait WindowedStatsExt: Iterator + Sized
+where
+    Self::Item: Into<f64>,
+{
+    fn windowed_stats(self, size: usize) -> WindowedStats<Self> {
+        WindowedStats::new(self, size)
+    }
+}
+
+impl<I> WindowedStatsExt for I
+where
+    I: Iterator,
+    I::Item: Into<f64>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(values: &[f64], size: usize) -> Vec<WindowStats> {
+        values.iter().copied().windowed_stats(size).collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        let tolerance = 1e-9 * expected.abs().max(1.0);
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn matches_direct_computation() {
+        let values = [1.0, 3.0, 2.0, 8.0, 4.0, 4.0, 1.0, -5.0, 0.5];
+        for size in 1..=values.len() {
+            let got = stats(&values, size);
+            assert_eq!(got.len(), values.len() - size + 1);
+            for (window, stats) in values.windows(size).zip(got) {
+                let n = size as f64;
+                let mean = window.iter().sum::<f64>() / n;
+                let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                assert_close(stats.mean, mean);
+                assert_close(stats.stddev, variance.sqrt());
+                assert_eq!(stats.min, window.iter().copied().fold(f64::MAX, f64::min));
+                assert_eq!(stats.max, window.iter().copied().fold(f64::MIN, f64::max));
+            }
+        }
+    }
+
+    #[test]
+    fn accepts_integer_items() {
+        let last = (1u32..=10).windowed_stats(4).last().unwrap();
+        assert_eq!(last.mean, 8.5);
+        assert_eq!((last.min, last.max), (7.0, 10.0));
+    }
+
+    #[test]
+    fn large_value_does_not_linger_after_leaving() {
+        let got = stats(&[1e16, 1.0, 1.0, 1.0, 1.0], 2);
+        for window in &got[1..] {
+            assert_eq!(
+                *window,
+                WindowStats {
+                    mean: 1.0,
+                    min: 1.0,
+                    max: 1.0,
+                    stddev: 0.0
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn stddev_survives_large_offset() {
+        let got = stats(&[1e9 + 1.0, 1e9 + 2.0, 1e9 + 3.0, 1e9 + 4.0], 4);
+        assert_close(got[0].mean, 1e9 + 2.5);
+        assert_close(got[0].stddev, 1.25f64.sqrt());
+    }
+
+    #[test]
+    fn large_finite_values_of_opposite_sign() {
+        let got = stats(&[1e200, -1e200, 1e200], 2);
+        for window in &got {
+            assert_eq!(window.mean, 0.0);
+            assert_eq!(window.stddev, f64::INFINITY);
+        }
+        assert_eq!((got[0].min, got[0].max), (-1e200, 1e200));
+
+        let extreme = stats(&[f64::MAX, -f64::MAX, 2.0, 4.0], 2);
+        assert_eq!(extreme[0].mean, 0.0);
+        assert_eq!(extreme[0].stddev, f64::INFINITY);
+        assert_eq!(extreme[2].mean, 3.0);
+        assert_eq!(extreme[2].stddev, 1.0);
+    }
+
+    #[test]
+    fn nan_only_affects_windows_containing_it() {
+        let got = stats(&[1.0, f64::NAN, 2.0, 3.0, 4.0], 2);
+        for window in &got[..2] {
+            assert!(window.mean.is_nan() && window.stddev.is_nan());
+            assert!(window.min.is_nan() && window.max.is_nan());
+        }
+        for (window, expected) in got[2..].iter().zip([2.5, 3.5]) {
+            assert_eq!(window.mean, expected);
+            assert_eq!(window.stddev, 0.5);
+        }
+    }
+
+    #[test]
+    fn infinity_only_affects_windows_containing_it() {
+        let got = stats(&[1.0, f64::INFINITY, 2.0, 3.0], 2);
+        for window in &got[..2] {
+            assert!(window.mean.is_nan() && window.stddev.is_nan());
+            assert_eq!(window.max, f64::INFINITY);
+        }
+        assert_eq!(got[2].mean, 2.5);
+        assert_eq!((got[2].min, got[2].max), (2.0, 3.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn zero_size_panics() {
+        let _ = [1.0f64].into_iter().windowed_stats(0);
+    }
+}
@@ -0,0 +1,75 @@
+use traits::{Registrar, Registry};
+
+mod greeting {
+    use traits::{Plugin, Registry, RegistryError};
+
+    struct Hello;
+
+    impl Plugin for Hello {
+        fn name(&self) -> &str {
+            "hello"
+        }
+
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+
+        fn execute(&self, args: &[&str]) -> Result<String, String> {
+            Ok(format!("hello {}", args.join(" ")))
+        }
+    }
+
+    pub fn register(registry: &mut Registry) -> Result<(), RegistryError> {
+        registry.register(Box::new(Hello), 0)
+    }
+}
+
+mod math {
+    use traits::{Plugin, Registry, RegistryError};
+
+    struct Sum;
+
+    impl Plugin for Sum {
+        fn name(&self) -> &str {
+            "sum"
+        }
+
+        fn version(&self) -> &str {
+            "0.2.0"
+        }
+
+        fn execute(&self, args: &[&str]) -> Result<String, String> {
+            let mut total = 0i64;
+            for arg in args {
+                let value = arg.parse::<i64>().map_err(|e| format!("{arg}: {e}"))?;
+                total = total
+                    .checked_add(value)
+                    .ok_or_else(|| "sum overflows i64".to_string())?;
+            }
+            Ok(total.to_string())
+        }
+    }
+
+    pub fn register(registry: &mut Registry) -> Result<(), RegistryError> {
+        registry.register(Box::new(Sum), 10)
+    }
+}
+
+// Every module with plugins gets one entry here; a new module means a new entry.
+const REGISTRARS: &[Registrar] = &[greeting::register, math::register];
+
+fn main() {
+    let mut registry = Registry::from_registrars(REGISTRARS).unwrap();
+    for plugin in registry.iter() {
+        println!("{} v{}", plugin.name(), plugin.version());
+    }
+
+    println!("{:?}", registry.execute("hello", &["world"]));
+    println!("{:?}", registry.execute("sum", &["1", "2", "x"]));
+    println!(
+        "{:?}",
+        registry.execute("sum", &["9223372036854775807", "1"])
+    );
+    println!("{:?}", registry.execute("missing", &[]));
+    println!("{:?}", greeting::register(&mut registry));
+}
@@ -0,0 +1,211 @@
+use std::fmt;
+
+pub trait Plugin: Send + Sync {
+    fn name(&self) -> &str;
+    fn version(&self) -> &str;
+    fn execute(&self, args: &[&str]) -> Result<String, String>;
+}
+
+/// A function each module exposes to add its plugins to a [`Registry`].
+///
+/// Registrars are listed explicitly and passed to [`Registry::from_registrars`];
+/// there is no automatic discovery. The list names modules, not plugin types,
+/// so a module can add or rename plugins without touching it.
+pub type Registrar = fn(&mut Registry) -> Result<(), RegistryError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    DuplicateName(String),
+    NotFound(String),
+    /// The plugin ran but returned an error.
+    Plugin(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::DuplicateName(name) => {
+                write!(f, "plugin {name:?} is already registered")
+            }
+            RegistryError::NotFound(name) => write!(f, "no plugin named {name:?}"),
+            RegistryError::Plugin(message) => write!(f, "plugin failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+struct Entry {
+    priority: i32,
+    plugin: Box<dyn Plugin>,
+}
+
+/// Plugins ordered by descending priority; equal priorities keep registration order.
+#[derive(Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_registrars(registrars: &[Registrar]) -> Result<Self, RegistryError> {
+        let mut registry = Self::new();
+        for registrar in registrars {
+            registrar(&mut registry)?;
+        }
+        Ok(registry)
+    }
+
+    pub fn register(
+        &mut self,
+        plugin: Box<dyn Plugin>,
+        priority: i32,
+    ) -> Result<(), RegistryError> {
+        if self.get(plugin.name()).is_some() {
+            return Err(RegistryError::DuplicateName(plugin.name().to_string()));
+        }
+        let at = self
+            .entries
+            .iter()
+            .position(|entry| entry.priority < priority)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(at, Entry { priority, plugin });
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, name: &str) -> Option<Box<dyn Plugin>> {
+        let at = self
+            .entries
+            .iter()
+            .position(|entry| entry.plugin.name() == name)?;
+        Some(self.entries.remove(at).plugin)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Plugin> {
+        self.iter().find(|plugin| plugin.name() == name)
+    }
+
+    pub fn execute(&self, name: &str, args: &[&str]) -> Result<String, RegistryError> {
+        let plugin = self
+            .get(name)
+            .ok_or_else(|| RegistryError::NotFound(name.to_string()))?;
+        plugin.execute(args).map_err(RegistryError::Plugin)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Plugin> {
+        self.entries.iter().map(|entry| entry.plugin.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Echoes its arguments, or fails when given none.
+    struct Echo(&'static str);
+
+    impl Plugin for Echo {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn version(&self) -> &str {
+            "0.0.0"
+        }
+
+        fn execute(&self, args: &[&str]) -> Result<String, String> {
+            if args.is_empty() {
+                return Err("no arguments".to_string());
+            }
+            Ok(args.join(" "))
+        }
+    }
+
+    fn names(registry: &Registry) -> Vec<&str> {
+        registry.iter().map(|plugin| plugin.name()).collect()
+    }
+
+    #[test]
+    fn orders_by_descending_priority_with_stable_ties() {
+        let mut registry = Registry::new();
+        for (name, priority) in [("a", 0), ("b", 5), ("c", 0), ("d", 5), ("e", -1)] {
+            registry.register(Box::new(Echo(name)), priority).unwrap();
+        }
+        assert_eq!(names(&registry), ["b", "d", "a", "c", "e"]);
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(Echo("a")), 0).unwrap();
+        assert_eq!(
+            registry.register(Box::new(Echo("a")), 10),
+            Err(RegistryError::DuplicateName("a".to_string()))
+        );
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn unregister_removes_plugin() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(Echo("a")), 0).unwrap();
+        registry.register(Box::new(Echo("b")), 0).unwrap();
+        assert_eq!(
+            registry.unregister("a").map(|p| p.name().to_string()),
+            Some("a".to_string())
+        );
+        assert!(registry.unregister("a").is_none());
+        assert_eq!(names(&registry), ["b"]);
+        registry.register(Box::new(Echo("a")), 0).unwrap();
+        assert_eq!(names(&registry), ["b", "a"]);
+    }
+
+    #[test]
+    fn execute_reports_missing_and_failing_plugins() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(Echo("echo")), 0).unwrap();
+        assert_eq!(
+            registry.execute("echo", &["hi", "there"]),
+            Ok("hi there".to_string())
+        );
+        assert_eq!(
+            registry.execute("echo", &[]),
+            Err(RegistryError::Plugin("no arguments".to_string()))
+        );
+        assert_eq!(
+            registry.execute("missing", &["x"]),
+            Err(RegistryError::NotFound("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn from_registrars_stops_at_first_failure() {
+        fn first(registry: &mut Registry) -> Result<(), RegistryError> {
+            registry.register(Box::new(Echo("a")), 0)
+        }
+        fn duplicate(registry: &mut Registry) -> Result<(), RegistryError> {
+            registry.register(Box::new(Echo("a")), 0)
+        }
+        fn never(_: &mut Registry) -> Result<(), RegistryError> {
+            panic!("registrars after a failure must not run");
+        }
+
+        let registry = Registry::from_registrars(&[first]).unwrap();
+        assert_eq!(names(&registry), ["a"]);
+        assert_eq!(
+            Registry::from_registrars(&[first, duplicate, never]).err(),
+            Some(RegistryError::DuplicateName("a".to_string()))
+        );
+    }
+}